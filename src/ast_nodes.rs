@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use crate::spans::Span;
+
+pub type NodeStream = Vec<NodeSpan>;
+
+#[derive(Debug, Clone)]
+pub struct NodeSpan {
+    pub node: Node,
+    pub span: Span,
+}
+
+impl NodeSpan {
+    /// Marks an expression as the tail/result value of the block that contains it.
+    pub fn wrap_in_result(self) -> NodeSpan {
+        let span = self.span;
+        ResultNode {
+            value: Box::new(self),
+        }
+        .to_nodespan(span)
+    }
+}
+
+pub trait ToNodeSpan {
+    fn to_nodespan(self, span: Span) -> NodeSpan;
+}
+
+#[derive(Debug, Clone)]
+pub enum Node {
+    Value(Value),
+    Variable(Variable),
+    Declaration(Declaration),
+    Assignment(Assignment),
+    UnaryNode(UnaryNode),
+    BinaryNode(BinaryNode),
+    Call(Call),
+    Block(Block),
+    DoBlock(DoBlock),
+    Loop(Loop),
+    Branch(Branch),
+    Function(Function),
+    FieldAccess(FieldAccess),
+    Index(Index),
+    RecordLiteral(RecordLiteral),
+    Result(ResultNode),
+}
+
+macro_rules! impl_to_nodespan {
+    ($ty:ident => $variant:ident) => {
+        impl ToNodeSpan for $ty {
+            fn to_nodespan(self, span: Span) -> NodeSpan {
+                NodeSpan {
+                    node: Node::$variant(self),
+                    span,
+                }
+            }
+        }
+    };
+}
+
+impl_to_nodespan!(Value => Value);
+impl_to_nodespan!(Variable => Variable);
+impl_to_nodespan!(Declaration => Declaration);
+impl_to_nodespan!(Assignment => Assignment);
+impl_to_nodespan!(UnaryNode => UnaryNode);
+impl_to_nodespan!(BinaryNode => BinaryNode);
+impl_to_nodespan!(Call => Call);
+impl_to_nodespan!(Block => Block);
+impl_to_nodespan!(DoBlock => DoBlock);
+impl_to_nodespan!(Loop => Loop);
+impl_to_nodespan!(Branch => Branch);
+impl_to_nodespan!(Function => Function);
+impl_to_nodespan!(FieldAccess => FieldAccess);
+impl_to_nodespan!(Index => Index);
+impl_to_nodespan!(RecordLiteral => RecordLiteral);
+impl_to_nodespan!(ResultNode => Result);
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    Null,
+    Function(Function),
+    Record(HashMap<String, Value>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    NOT,
+    NEGATIVE,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    ADD,
+    SUBTRACT,
+    MULTIPLY,
+    DIVIDE,
+    MODULO,
+    GREATER,
+    GREATER_EQUAL,
+    LESSER,
+    LESSER_EQUAL,
+    ISEQUAL,
+    ISDIFERENT,
+    AND,
+    OR,
+}
+
+#[derive(Debug, Clone)]
+pub struct Variable {
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Declaration {
+    pub var_name: String,
+    pub value: Box<NodeSpan>,
+}
+
+/// `target = value`. `target` is restricted (at eval time) to a bare
+/// variable or a `.field`/`[index]` chain bottoming out at one.
+#[derive(Debug, Clone)]
+pub struct Assignment {
+    pub target: Box<NodeSpan>,
+    pub value: Box<NodeSpan>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnaryNode {
+    pub kind: UnaryOp,
+    pub object: Box<NodeSpan>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BinaryNode {
+    pub kind: BinaryOp,
+    pub left: Box<NodeSpan>,
+    pub right: Box<NodeSpan>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Call {
+    pub args: Box<NodeStream>,
+    pub callee: Box<NodeSpan>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub body: Box<NodeStream>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DoBlock {
+    pub body: Box<NodeSpan>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Loop {
+    pub proc: Box<NodeSpan>,
+}
+
+/// `func name(a, b, c) { ... }`. Doubles as the runtime representation of a
+/// callable `Value` once evaluated and bound in scope.
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub name: String,
+    pub params: Vec<String>,
+    pub body: Box<Block>,
+    /// Scope frames captured at the point this function was defined, so a
+    /// call is rooted at its definition site instead of whatever happens to
+    /// be on the caller's stack. Empty for the bare `Function` node the
+    /// parser produces; filled in by the interpreter once `func` is
+    /// evaluated into a `Value::Function`.
+    pub captured: Vec<HashMap<String, Value>>,
+}
+
+/// `if <condition> { then_block } else { else_block }`. `else_block` may
+/// itself be a `Branch` to support `else if` chaining, or `None` if there's
+/// no `else` clause at all.
+#[derive(Debug, Clone)]
+pub struct Branch {
+    pub condition: Box<NodeSpan>,
+    pub then_block: Box<NodeSpan>,
+    pub else_block: Option<Box<NodeSpan>>,
+}
+
+/// Marks the value a block yields to whatever evaluates it.
+#[derive(Debug, Clone)]
+pub struct ResultNode {
+    pub value: Box<NodeSpan>,
+}
+
+/// `base.field`, a high-precedence postfix operator folded into the same
+/// loop as calls and indexing.
+#[derive(Debug, Clone)]
+pub struct FieldAccess {
+    pub base: Box<NodeSpan>,
+    pub field: String,
+}
+
+/// `base[index]`, a high-precedence postfix operator folded into the same
+/// loop as calls and field access.
+#[derive(Debug, Clone)]
+pub struct Index {
+    pub base: Box<NodeSpan>,
+    pub index: Box<NodeSpan>,
+}
+
+/// `{ name: expr, name: expr }`, producing a `Value::Record` keyed by field name.
+#[derive(Debug, Clone)]
+pub struct RecordLiteral {
+    pub fields: Vec<(String, NodeSpan)>,
+}
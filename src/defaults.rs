@@ -0,0 +1,20 @@
+use crate::ast_nodes::Value;
+
+/// Renders a runtime `Value` the way the REPL shows it.
+pub fn val_to_str(value: &Value) -> String {
+    match value {
+        Value::Str(s) => s.clone(),
+        Value::Num(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Null => "null".to_string(),
+        Value::Function(f) => format!("<func {}>", f.name),
+        Value::Record(fields) => {
+            let mut entries: Vec<String> = fields
+                .iter()
+                .map(|(key, value)| format!("{key}: {}", val_to_str(value)))
+                .collect();
+            entries.sort();
+            format!("{{ {} }}", entries.join(", "))
+        }
+    }
+}
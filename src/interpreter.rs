@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+
+use crate::ast_nodes::{Block, BinaryOp, Branch, Call, FieldAccess, Index, Node, NodeSpan, RecordLiteral, UnaryOp, Value};
+use crate::lang_errors::LangError;
+
+/// A chain of lexical scopes, innermost last.
+pub struct Scope {
+    frames: Vec<HashMap<String, Value>>,
+}
+
+impl Scope {
+    pub fn new() -> Self {
+        Self {
+            frames: vec![HashMap::new()],
+        }
+    }
+
+    /// Rebuilds a scope from a function's captured frames, with a fresh
+    /// frame on top for its parameters/locals. This is what roots a call at
+    /// its definition site instead of the caller's stack.
+    fn from_captured(captured: &[HashMap<String, Value>]) -> Self {
+        let mut frames = captured.to_vec();
+        frames.push(HashMap::new());
+        Self { frames }
+    }
+
+    /// A snapshot of every frame currently in scope, for a function
+    /// definition to capture as its closure environment.
+    fn snapshot(&self) -> Vec<HashMap<String, Value>> {
+        self.frames.clone()
+    }
+
+    fn push(&mut self) {
+        self.frames.push(HashMap::new());
+    }
+
+    fn pop(&mut self) {
+        self.frames.pop();
+    }
+
+    fn define(&mut self, name: String, value: Value) {
+        self.frames
+            .last_mut()
+            .expect("scope always has at least one frame")
+            .insert(name, value);
+    }
+
+    fn get(&self, name: &str) -> Option<Value> {
+        self.frames.iter().rev().find_map(|frame| frame.get(name).cloned())
+    }
+
+    fn assign(&mut self, name: &str, value: Value) -> bool {
+        for frame in self.frames.iter_mut().rev() {
+            if frame.contains_key(name) {
+                frame.insert(name.to_string(), value);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+pub struct Interpreter {
+    ast: Block,
+    scope: Scope,
+}
+
+impl Interpreter {
+    pub fn new(ast: Block) -> Self {
+        Self {
+            ast,
+            scope: Scope::new(),
+        }
+    }
+
+    pub fn execute(&mut self) -> Result<(), LangError> {
+        for statement in self.ast.body.iter() {
+            eval(statement, &mut self.scope)?;
+        }
+        Ok(())
+    }
+
+    /// Evaluates a single expression in a fresh scope, for the REPL.
+    pub fn execute_node(node: NodeSpan) -> Result<(Value, ()), LangError> {
+        let mut scope = Scope::new();
+        let value = eval(&node, &mut scope)?;
+        Ok((value, ()))
+    }
+}
+
+fn eval(node: &NodeSpan, scope: &mut Scope) -> Result<Value, LangError> {
+    match &node.node {
+        Node::Value(value) => Ok(value.clone()),
+        Node::Variable(variable) => scope
+            .get(&variable.name)
+            .ok_or_else(|| LangError::new(format!("undefined variable `{}`", variable.name), node.span)),
+        Node::Declaration(declaration) => {
+            let value = eval(&declaration.value, scope)?;
+            scope.define(declaration.var_name.clone(), value);
+            Ok(Value::Null)
+        }
+        Node::Assignment(assignment) => {
+            let value = eval(&assignment.value, scope)?;
+            assign_target(&assignment.target, value.clone(), scope)?;
+            Ok(value)
+        }
+        Node::UnaryNode(unary) => {
+            let object = eval(&unary.object, scope)?;
+            eval_unary(unary.kind, object, node)
+        }
+        Node::BinaryNode(binary) => eval_binary(binary, scope, node),
+        Node::Call(call) => eval_call(call, scope, node),
+        Node::Block(block) => eval_block(block, scope),
+        Node::DoBlock(do_block) => eval(&do_block.body, scope),
+        Node::Loop(loop_node) => loop {
+            eval(&loop_node.proc, scope)?;
+        },
+        Node::Branch(branch) => eval_branch(branch, scope, node),
+        Node::Function(function) => {
+            let mut closure = function.clone();
+            closure.captured = scope.snapshot();
+            scope.define(function.name.clone(), Value::Function(closure));
+            Ok(Value::Null)
+        }
+        Node::FieldAccess(access) => eval_field_access(access, scope, node),
+        Node::Index(index) => eval_index(index, scope, node),
+        Node::RecordLiteral(record) => eval_record_literal(record, scope),
+        Node::Result(result) => eval(&result.value, scope),
+    }
+}
+
+/// Writes `value` through an assignment target: a bare variable, or a
+/// `.field`/`[index]` chain that bottoms out at one. Since values are plain
+/// clones (no shared references anywhere in this interpreter), a nested
+/// write re-reads, patches, and writes back the whole record at each step.
+fn assign_target(target: &NodeSpan, value: Value, scope: &mut Scope) -> Result<(), LangError> {
+    match &target.node {
+        Node::Variable(variable) => {
+            if !scope.assign(&variable.name, value) {
+                return Err(LangError::new(
+                    format!("assignment to undefined variable `{}`", variable.name),
+                    target.span,
+                ));
+            }
+            Ok(())
+        }
+        Node::FieldAccess(access) => {
+            let mut fields = eval_record(&access.base, scope)?;
+            fields.insert(access.field.clone(), value);
+            assign_target(&access.base, Value::Record(fields), scope)
+        }
+        Node::Index(index) => {
+            let Value::Str(key) = eval(&index.index, scope)? else {
+                return Err(LangError::new("record index must be a string", index.index.span));
+            };
+            let mut fields = eval_record(&index.base, scope)?;
+            fields.insert(key, value);
+            assign_target(&index.base, Value::Record(fields), scope)
+        }
+        _ => Err(LangError::new("invalid assignment target", target.span)),
+    }
+}
+
+fn eval_record(node: &NodeSpan, scope: &mut Scope) -> Result<HashMap<String, Value>, LangError> {
+    match eval(node, scope)? {
+        Value::Record(fields) => Ok(fields),
+        _ => Err(LangError::new("field/index assignment target must be a record", node.span)),
+    }
+}
+
+fn eval_record_literal(record: &RecordLiteral, scope: &mut Scope) -> Result<Value, LangError> {
+    let mut fields = HashMap::new();
+    for (key, value) in record.fields.iter() {
+        fields.insert(key.clone(), eval(value, scope)?);
+    }
+    Ok(Value::Record(fields))
+}
+
+fn eval_field_access(access: &FieldAccess, scope: &mut Scope, node: &NodeSpan) -> Result<Value, LangError> {
+    let Value::Record(fields) = eval(&access.base, scope)? else {
+        return Err(LangError::new("field access is only supported on records", node.span));
+    };
+    fields
+        .get(&access.field)
+        .cloned()
+        .ok_or_else(|| LangError::new(format!("record has no field `{}`", access.field), node.span))
+}
+
+fn eval_index(index: &Index, scope: &mut Scope, node: &NodeSpan) -> Result<Value, LangError> {
+    let Value::Record(fields) = eval(&index.base, scope)? else {
+        return Err(LangError::new("indexing is only supported on records", node.span));
+    };
+    let Value::Str(key) = eval(&index.index, scope)? else {
+        return Err(LangError::new("record index must be a string", node.span));
+    };
+    fields
+        .get(&key)
+        .cloned()
+        .ok_or_else(|| LangError::new(format!("record has no field `{key}`"), node.span))
+}
+
+fn eval_branch(branch: &Branch, scope: &mut Scope, node: &NodeSpan) -> Result<Value, LangError> {
+    let Value::Bool(condition) = eval(&branch.condition, scope)? else {
+        return Err(LangError::new("condition of `if` must be a boolean", node.span));
+    };
+    if condition {
+        eval(&branch.then_block, scope)
+    } else if let Some(else_block) = &branch.else_block {
+        eval(else_block, scope)
+    } else {
+        Ok(Value::Null)
+    }
+}
+
+fn eval_block(block: &Block, scope: &mut Scope) -> Result<Value, LangError> {
+    scope.push();
+    let mut value = Value::Null;
+    for statement in block.body.iter() {
+        value = eval(statement, scope)?;
+    }
+    scope.pop();
+    Ok(value)
+}
+
+fn eval_call(call: &Call, scope: &mut Scope, node: &NodeSpan) -> Result<Value, LangError> {
+    let callee = eval(&call.callee, scope)?;
+    let Value::Function(function) = callee else {
+        return Err(LangError::new("only functions can be called", node.span));
+    };
+    if call.args.len() != function.params.len() {
+        return Err(LangError::new(
+            format!(
+                "function `{}` expects {} argument(s) but got {}",
+                function.name,
+                function.params.len(),
+                call.args.len()
+            ),
+            node.span,
+        ));
+    }
+    let mut args = Vec::with_capacity(call.args.len());
+    for arg in call.args.iter() {
+        args.push(eval(arg, scope)?);
+    }
+
+    let mut call_scope = Scope::from_captured(&function.captured);
+    for (param, arg) in function.params.iter().zip(args) {
+        call_scope.define(param.clone(), arg);
+    }
+    eval_block(&function.body, &mut call_scope)
+}
+
+fn eval_unary(kind: UnaryOp, object: Value, node: &NodeSpan) -> Result<Value, LangError> {
+    match (kind, object) {
+        (UnaryOp::NOT, Value::Bool(b)) => Ok(Value::Bool(!b)),
+        (UnaryOp::NEGATIVE, Value::Num(n)) => Ok(Value::Num(-n)),
+        (kind, value) => Err(LangError::new(
+            format!("cannot apply {kind:?} to {value:?}"),
+            node.span,
+        )),
+    }
+}
+
+fn eval_binary(
+    binary: &crate::ast_nodes::BinaryNode,
+    scope: &mut Scope,
+    node: &NodeSpan,
+) -> Result<Value, LangError> {
+    if binary.kind == BinaryOp::AND || binary.kind == BinaryOp::OR {
+        let Value::Bool(left) = eval(&binary.left, scope)? else {
+            return Err(LangError::new("operands of `and`/`or` must be booleans", node.span));
+        };
+        if binary.kind == BinaryOp::AND && !left {
+            return Ok(Value::Bool(false));
+        }
+        if binary.kind == BinaryOp::OR && left {
+            return Ok(Value::Bool(true));
+        }
+        let Value::Bool(right) = eval(&binary.right, scope)? else {
+            return Err(LangError::new("operands of `and`/`or` must be booleans", node.span));
+        };
+        return Ok(Value::Bool(right));
+    }
+
+    let left = eval(&binary.left, scope)?;
+    let right = eval(&binary.right, scope)?;
+    match (binary.kind, left, right) {
+        (BinaryOp::ADD, Value::Num(a), Value::Num(b)) => Ok(Value::Num(a + b)),
+        (BinaryOp::ADD, Value::Str(a), Value::Str(b)) => Ok(Value::Str(a + &b)),
+        (BinaryOp::SUBTRACT, Value::Num(a), Value::Num(b)) => Ok(Value::Num(a - b)),
+        (BinaryOp::MULTIPLY, Value::Num(a), Value::Num(b)) => Ok(Value::Num(a * b)),
+        (BinaryOp::DIVIDE, Value::Num(a), Value::Num(b)) => Ok(Value::Num(a / b)),
+        (BinaryOp::MODULO, Value::Num(a), Value::Num(b)) => Ok(Value::Num(a % b)),
+        (BinaryOp::GREATER, Value::Num(a), Value::Num(b)) => Ok(Value::Bool(a > b)),
+        (BinaryOp::GREATER_EQUAL, Value::Num(a), Value::Num(b)) => Ok(Value::Bool(a >= b)),
+        (BinaryOp::LESSER, Value::Num(a), Value::Num(b)) => Ok(Value::Bool(a < b)),
+        (BinaryOp::LESSER_EQUAL, Value::Num(a), Value::Num(b)) => Ok(Value::Bool(a <= b)),
+        (BinaryOp::ISEQUAL, a, b) => Ok(Value::Bool(values_equal(&a, &b))),
+        (BinaryOp::ISDIFERENT, a, b) => Ok(Value::Bool(!values_equal(&a, &b))),
+        (kind, a, b) => Err(LangError::new(
+            format!("cannot apply {kind:?} to {a:?} and {b:?}"),
+            node.span,
+        )),
+    }
+}
+
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Str(a), Value::Str(b)) => a == b,
+        (Value::Num(a), Value::Num(b)) => a == b,
+        (Value::Bool(a), Value::Bool(b)) => a == b,
+        (Value::Null, Value::Null) => true,
+        _ => false,
+    }
+}
@@ -0,0 +1,146 @@
+use std::io::IsTerminal;
+
+use crate::spans::Span;
+use colored::Colorize;
+
+/// Holds the original source text so diagnostics can be rendered against it.
+#[derive(Clone)]
+pub struct ErrorBuilder {
+    input: String,
+}
+
+impl ErrorBuilder {
+    pub fn new(input: String) -> Self {
+        Self { input }
+    }
+
+    /// The 1-based line number, and the byte range of the line itself
+    /// (excluding its trailing newline), that `pos` falls on.
+    fn line_bounds(&self, pos: usize) -> (usize, usize, usize) {
+        let pos = pos.min(self.input.len());
+        let line_start = self.input[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = self.input[pos..]
+            .find('\n')
+            .map(|i| pos + i)
+            .unwrap_or(self.input.len());
+        let line_no = self.input[..line_start].matches('\n').count() + 1;
+        (line_no, line_start, line_end)
+    }
+
+    /// Renders `message` with the source excerpt at `span`: a line-number
+    /// gutter followed by the offending source line, and a caret run
+    /// underneath exactly the span's columns. A span crossing newlines gets
+    /// one gutter line and one caret run per line it touches; a span at
+    /// EOF (start == end == end of input) gets a single caret pointing just
+    /// past the last character. Colors are skipped when stdout isn't a
+    /// terminal.
+    pub fn build(&self, message: &str, span: Span) -> String {
+        let colorize = std::io::stdout().is_terminal();
+        let start = span.0.min(self.input.len());
+        let end = span.1.min(self.input.len()).max(start);
+
+        let header = if colorize {
+            format!("{} {message}", "error:".red().bold())
+        } else {
+            format!("error: {message}")
+        };
+
+        let mut rendered = header;
+        let mut pos = start;
+        loop {
+            let (line_no, line_start, line_end) = self.line_bounds(pos);
+            let line_text = &self.input[line_start..line_end];
+            let seg_start = pos - line_start;
+            let seg_end = (end.min(line_end) - line_start).max(seg_start + 1);
+
+            let gutter = format!("{line_no} | ");
+            let underline = format!(
+                "{}{}",
+                " ".repeat(gutter.len() + seg_start),
+                "^".repeat(seg_end - seg_start)
+            );
+            let underline = if colorize {
+                underline.red().bold().to_string()
+            } else {
+                underline
+            };
+
+            rendered.push('\n');
+            rendered.push_str(&gutter);
+            rendered.push_str(line_text);
+            rendered.push('\n');
+            rendered.push_str(&underline);
+
+            if line_end + 1 >= end {
+                break;
+            }
+            pos = line_end + 1;
+        }
+
+        rendered
+    }
+
+    pub fn emit(&self, message: &str, span: Span) {
+        println!("{}", self.build(message, span));
+    }
+}
+
+/// A single error produced while parsing or interpreting, carrying enough
+/// information to be rendered later by an `ErrorBuilder`.
+#[derive(Debug, Clone)]
+pub struct LangError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl LangError {
+    pub fn new(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+
+    pub fn print_msg(&self, err_out: ErrorBuilder) {
+        println!("{}", err_out.build(&self.message, self.span));
+    }
+}
+
+/// A single problem found while parsing, carrying the span it refers to.
+/// Distinct from `LangError` only in name: diagnostics are *collected*
+/// rather than bailing out immediately, so a parse can surface several.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub message: String,
+    pub span: Span,
+}
+
+/// Accumulates `Diagnostic`s as parsing runs instead of aborting on the
+/// first problem, so a single `batch_parse` can report every malformed
+/// statement it finds rather than just the first.
+#[derive(Debug, Clone, Default)]
+pub struct Handler {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Handler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a diagnostic; does not stop parsing.
+    pub fn report(&mut self, message: impl Into<String>, span: Span) {
+        self.diagnostics.push(Diagnostic {
+            message: message.into(),
+            span,
+        });
+    }
+
+    pub fn has_errors(&self) -> bool {
+        !self.diagnostics.is_empty()
+    }
+
+    pub fn into_diagnostics(self) -> Vec<Diagnostic> {
+        self.diagnostics
+    }
+}
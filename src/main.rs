@@ -8,6 +8,7 @@ pub mod ast_nodes;
 pub mod defaults;
 pub mod interpreter;
 pub mod lang_errors;
+pub mod numeric;
 pub mod spans;
 pub mod tests;
 pub mod token_lexer;
@@ -40,9 +41,16 @@ fn main() {
 
 fn AST_from_file(file_path: String) {
     let source = fs::read_to_string(file_path).expect("Should have been able to read the file");
+    let err_out = ErrorBuilder::new(source.clone());
     let mut parser = Parser::new(source.as_str());
-    let ast = parser.batch_parse();
-    println!("{ast:#?}");
+    match parser.batch_parse() {
+        Ok(ast) => println!("{ast:#?}"),
+        Err(diagnostics) => {
+            for diagnostic in diagnostics {
+                err_out.emit(&diagnostic.message, diagnostic.span);
+            }
+        }
+    }
 }
 fn len2(args: Vec<String>) {
     match args[1].to_lowercase().as_str() {
@@ -64,7 +72,9 @@ fn execute_file(args: Vec<String>) {
     let mut parser = Parser::new(source.as_str());
     let ast_result = parser.batch_parse();
     let Ok(ast) = ast_result else {
-        ast_result.unwrap_err().print_msg(err_out);
+        for diagnostic in ast_result.unwrap_err() {
+            err_out.emit(&diagnostic.message, diagnostic.span);
+        }
         return;
     };
     let mut interpreter = Interpreter::new(ast);
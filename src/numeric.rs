@@ -0,0 +1,29 @@
+/// Parses a numeric literal's source text (as captured by the lexer,
+/// underscores and all) into an `f64` without ever panicking. A `0x`/`0o`/`0b`
+/// prefix selects the matching integer radix; anything else goes through the
+/// standard float parser, which already accepts `.` and `e`/`E` exponents.
+/// Returns a message describing the problem (not a `LangError`) so the
+/// caller can attach whatever span it has on hand.
+pub fn parse_number_literal(text: &str) -> Result<f64, String> {
+    let cleaned: String = text.chars().filter(|&c| c != '_').collect();
+
+    let radix = if cleaned.starts_with("0x") || cleaned.starts_with("0X") {
+        Some(16)
+    } else if cleaned.starts_with("0o") || cleaned.starts_with("0O") {
+        Some(8)
+    } else if cleaned.starts_with("0b") || cleaned.starts_with("0B") {
+        Some(2)
+    } else {
+        None
+    };
+
+    let Some(radix) = radix else {
+        return cleaned
+            .parse::<f64>()
+            .map_err(|_| format!("`{text}` is not a valid number"));
+    };
+
+    u64::from_str_radix(&cleaned[2..], radix)
+        .map(|n| n as f64)
+        .map_err(|_| format!("`{text}` is not a valid base-{radix} integer literal"))
+}
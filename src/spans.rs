@@ -0,0 +1,2 @@
+/// A byte-offset range `(start, end)` into the original source string.
+pub type Span = (usize, usize);
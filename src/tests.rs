@@ -0,0 +1,287 @@
+#[cfg(test)]
+mod parser_tests {
+    use crate::ast_nodes::{BinaryOp, Node};
+    use crate::token_parser::Parser;
+
+    fn parse(source: &str) -> Node {
+        let mut parser = Parser::new(source);
+        parser.batch_parse_expr().expect("expression should parse").node
+    }
+
+    #[test]
+    fn multiplication_binds_tighter_than_addition() {
+        // `1 + 2 * 3` should be `1 + (2 * 3)`, not `(1 + 2) * 3`.
+        let Node::BinaryNode(add) = parse("1 + 2 * 3") else {
+            panic!("expected a binary node");
+        };
+        assert_eq!(add.kind, BinaryOp::ADD);
+        let Node::BinaryNode(mul) = add.right.node else {
+            panic!("right side of + should be a multiplication");
+        };
+        assert_eq!(mul.kind, BinaryOp::MULTIPLY);
+    }
+
+    #[test]
+    fn comparisons_bind_tighter_than_and() {
+        // `a and b == c` should be `a and (b == c)`.
+        let Node::BinaryNode(and) = parse("true and 1 == 1") else {
+            panic!("expected a binary node");
+        };
+        assert_eq!(and.kind, BinaryOp::AND);
+        let Node::BinaryNode(eq) = and.right.node else {
+            panic!("right side of `and` should be a comparison");
+        };
+        assert_eq!(eq.kind, BinaryOp::ISEQUAL);
+    }
+
+    #[test]
+    fn subtraction_is_left_associative() {
+        // `1 - 2 - 3` should be `(1 - 2) - 3`.
+        let Node::BinaryNode(outer) = parse("1 - 2 - 3") else {
+            panic!("expected a binary node");
+        };
+        assert_eq!(outer.kind, BinaryOp::SUBTRACT);
+        let Node::BinaryNode(_) = outer.left.node else {
+            panic!("left side of the outer subtraction should itself be a subtraction");
+        };
+    }
+
+    #[test]
+    fn unary_minus_and_not_parse_their_operand() {
+        let Node::UnaryNode(neg) = parse("-5") else {
+            panic!("expected a unary node");
+        };
+        assert_eq!(neg.kind, crate::ast_nodes::UnaryOp::NEGATIVE);
+        let Node::Value(crate::ast_nodes::Value::Num(n)) = neg.object.node else {
+            panic!("expected the operand to be a number");
+        };
+        assert_eq!(n, 5.0);
+
+        let Node::BinaryNode(sub) = parse("1 - -5") else {
+            panic!("expected a binary node");
+        };
+        let Node::UnaryNode(_) = sub.right.node else {
+            panic!("right side of the subtraction should be a unary negation");
+        };
+
+        let Node::UnaryNode(not) = parse("not true") else {
+            panic!("expected a unary node");
+        };
+        assert_eq!(not.kind, crate::ast_nodes::UnaryOp::NOT);
+    }
+
+    #[test]
+    fn line_comment_is_ignored() {
+        let Node::Value(crate::ast_nodes::Value::Num(n)) = parse("1 // + 2\n") else {
+            panic!("expected a number");
+        };
+        assert_eq!(n, 1.0);
+    }
+
+    #[test]
+    fn nested_block_comment_closes_on_outer_terminator() {
+        let Node::BinaryNode(add) = parse("1 /* /* nested */ still a comment */ + 2") else {
+            panic!("expected a binary node");
+        };
+        assert_eq!(add.kind, BinaryOp::ADD);
+    }
+
+    #[test]
+    fn else_if_chains_into_a_nested_branch() {
+        let Node::Branch(outer) = parse("if true { 1 } else if false { 2 } else { 3 }") else {
+            panic!("expected a branch node");
+        };
+        let Node::Branch(_) = outer.else_block.expect("else branch").node else {
+            panic!("`else if` should parse as a nested branch");
+        };
+    }
+
+    #[test]
+    fn function_parses_name_and_params_in_order() {
+        let Node::Function(function) = parse("func add(a, b) { a + b }") else {
+            panic!("expected a function node");
+        };
+        assert_eq!(function.name, "add");
+        assert_eq!(function.params, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn batch_parse_collects_every_diagnostic_instead_of_stopping_at_the_first() {
+        let mut parser = Parser::new("func add(a, b { a + b }\nfunc sub(1) { a }\n");
+        let diagnostics = parser.batch_parse().expect_err("malformed source should fail to parse");
+        assert_eq!(diagnostics.len(), 2);
+    }
+
+    #[test]
+    fn vardef_span_covers_the_initializer_not_just_the_equals_sign() {
+        let mut parser = Parser::new("var x = 1 + 2\n");
+        let block = parser.batch_parse().expect("should parse");
+        assert_eq!(block.body[0].span, (4, 13));
+    }
+
+    #[test]
+    fn blank_lines_between_top_level_statements_are_not_errors() {
+        let mut parser = Parser::new("var x = 1\n\nvar y = 2\n");
+        let block = parser.batch_parse().expect("blank lines should be skipped, not reported");
+        assert_eq!(block.body.len(), 2);
+    }
+
+    #[test]
+    fn field_index_and_call_chain_tighter_than_any_operator() {
+        // `obj.a[b]() + 1` should be `(((obj.a)[b])()) + 1`.
+        let Node::BinaryNode(add) = parse("obj.a[b]() + 1") else {
+            panic!("expected a binary node");
+        };
+        let Node::Call(call) = add.left.node else {
+            panic!("left side of + should be a call");
+        };
+        let Node::Index(index) = call.callee.node else {
+            panic!("call's callee should be an index");
+        };
+        let Node::FieldAccess(access) = index.base.node else {
+            panic!("index's base should be a field access");
+        };
+        assert_eq!(access.field, "a");
+    }
+
+    #[test]
+    fn record_literal_parses_fields_in_order() {
+        let Node::RecordLiteral(record) = parse("{ a: 1, b: 2 }") else {
+            panic!("expected a record literal");
+        };
+        assert_eq!(record.fields.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn assignment_target_may_be_a_field_chain() {
+        let Node::Assignment(assignment) = parse("obj.a = 1") else {
+            panic!("expected an assignment node");
+        };
+        let Node::FieldAccess(_) = assignment.target.node else {
+            panic!("assignment target should be a field access");
+        };
+    }
+
+    #[test]
+    fn hex_octal_and_binary_literals_parse_to_their_decimal_value() {
+        let Node::Value(crate::ast_nodes::Value::Num(n)) = parse("0xFF") else {
+            panic!("expected a number");
+        };
+        assert_eq!(n, 255.0);
+        let Node::Value(crate::ast_nodes::Value::Num(n)) = parse("0o17") else {
+            panic!("expected a number");
+        };
+        assert_eq!(n, 15.0);
+        let Node::Value(crate::ast_nodes::Value::Num(n)) = parse("0b101") else {
+            panic!("expected a number");
+        };
+        assert_eq!(n, 5.0);
+    }
+
+    #[test]
+    fn digit_group_underscores_are_ignored() {
+        let Node::Value(crate::ast_nodes::Value::Num(n)) = parse("1_000_000") else {
+            panic!("expected a number");
+        };
+        assert_eq!(n, 1_000_000.0);
+    }
+
+    #[test]
+    fn float_with_fraction_and_exponent_parses() {
+        let Node::Value(crate::ast_nodes::Value::Num(n)) = parse("1.5e3") else {
+            panic!("expected a number");
+        };
+        assert_eq!(n, 1500.0);
+    }
+
+    #[test]
+    fn trailing_dot_after_a_number_is_not_swallowed_into_the_literal() {
+        // `1.foo` is a malformed field access (numbers aren't records), but the
+        // `.` must still split off its own token rather than being read as a
+        // fraction with no digits after it.
+        let Node::FieldAccess(access) = parse("1.foo") else {
+            panic!("expected a field access node");
+        };
+        let Node::Value(crate::ast_nodes::Value::Num(n)) = access.base.node else {
+            panic!("expected the base to be a number");
+        };
+        assert_eq!(n, 1.0);
+        assert_eq!(access.field, "foo");
+    }
+
+    #[test]
+    fn overflowing_integer_literal_reports_a_diagnostic_instead_of_panicking() {
+        let mut parser = Parser::new("var x = 0xFFFFFFFFFFFFFFFFF\n");
+        let diagnostics = parser.batch_parse().expect_err("overflowing literal should fail to parse");
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn unterminated_block_comment_reports_a_diagnostic_instead_of_silently_succeeding() {
+        let mut parser = Parser::new("var x = 1 /* oops\n");
+        parser.batch_parse().expect_err("unterminated block comment should fail to parse");
+    }
+
+    #[test]
+    fn unrecognized_character_reports_a_diagnostic_instead_of_panicking() {
+        let mut parser = Parser::new("var x = 1\nvar y = x @ 2\n");
+        parser.batch_parse().expect_err("unrecognized character should fail to parse");
+    }
+}
+
+#[cfg(test)]
+mod interpreter_tests {
+    use crate::interpreter::Interpreter;
+    use crate::token_parser::Parser;
+
+    #[test]
+    fn function_bodies_are_rooted_at_their_definition_site_not_the_callers_locals() {
+        // `f` is defined at the top level, where `x` doesn't exist; it must
+        // not see `g`'s local `x` just because `g` happens to call it.
+        let mut parser = Parser::new(
+            "func f() { x + \"s\" }\nfunc g() { var x = 42\n f() }\nvar result = g()\n",
+        );
+        let ast = parser.batch_parse().expect("should parse");
+        let error = Interpreter::new(ast)
+            .execute()
+            .expect_err("f's `x` is undefined at its definition site");
+        assert!(
+            error.message.contains("undefined variable"),
+            "expected an undefined-variable error, got: {}",
+            error.message
+        );
+    }
+}
+
+#[cfg(test)]
+mod error_builder_tests {
+    use crate::lang_errors::ErrorBuilder;
+
+    #[test]
+    fn caret_run_lines_up_under_the_span() {
+        let err_out = ErrorBuilder::new("1 + oops".to_string());
+        let rendered = err_out.build("unexpected token", (4, 8));
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[1], "1 | 1 + oops");
+        assert_eq!(lines[2], format!("{}^^^^", " ".repeat(8)));
+    }
+
+    #[test]
+    fn span_at_eof_points_just_past_the_last_character() {
+        let err_out = ErrorBuilder::new("1 +".to_string());
+        let rendered = err_out.build("expected an expression", (3, 3));
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[1], "1 | 1 +");
+        assert_eq!(lines[2], format!("{}^", " ".repeat(7)));
+    }
+
+    #[test]
+    fn span_crossing_a_newline_underlines_each_line_it_touches() {
+        let err_out = ErrorBuilder::new("func f(\n1 2 3".to_string());
+        let rendered = err_out.build("unterminated parameter list", (7, 13));
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[1], "1 | func f(");
+        assert_eq!(lines[3], "2 | 1 2 3");
+    }
+}
@@ -0,0 +1,386 @@
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use crate::lang_errors::Diagnostic;
+use crate::tokens::{Token, TokenType};
+
+/// The digit-validity check for a `0x`/`0o`/`0b` prefix character, or `None`
+/// if `prefix` doesn't start one of those radixes.
+fn radix_digit_predicate(prefix: char) -> Option<fn(char) -> bool> {
+    Some(match prefix {
+        'x' | 'X' => |c: char| c.is_ascii_hexdigit(),
+        'o' | 'O' => |c: char| matches!(c, '0'..='7'),
+        'b' | 'B' => |c: char| matches!(c, '0' | '1'),
+        _ => return None,
+    })
+}
+
+/// Turns source text into a stream of `Token`s. Whitespace (other than
+/// newlines, which surface as `EOL`) is skipped silently. Comments come out
+/// as `LINE_COMMENT`/`BLOCK_COMMENT` tokens; `TokenIter` is what filters
+/// them away from the parser.
+#[derive(Clone)]
+pub struct Lexer<'input> {
+    input: &'input str,
+    chars: Peekable<CharIndices<'input>>,
+    len: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'input> Lexer<'input> {
+    pub fn new(input: &'input str) -> Self {
+        Self {
+            input,
+            chars: input.char_indices().peekable(),
+            len: input.len(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Diagnostics recorded by the lexer itself (unterminated block
+    /// comments, unrecognized characters). The token stream already
+    /// surfaces an `ERROR` token at the same spot; this is how that error
+    /// reaches a `Handler` instead of being printed or silently dropped.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    fn keyword(ident: &str) -> Option<TokenType> {
+        Some(match ident {
+            "var" => TokenType::VAR,
+            "do" => TokenType::DO,
+            "loop" => TokenType::LOOP,
+            "func" => TokenType::FUNC,
+            "if" => TokenType::IF,
+            "else" => TokenType::ELSE,
+            "true" => TokenType::TRUE,
+            "false" => TokenType::FALSE,
+            "and" => TokenType::AND,
+            "or" => TokenType::OR,
+            "not" => TokenType::NOT,
+            _ => return None,
+        })
+    }
+
+    fn lex_identifier(&mut self, start: usize) -> Token {
+        let mut end = start;
+        while let Some(&(idx, ch)) = self.chars.peek() {
+            if ch.is_alphanumeric() || ch == '_' {
+                end = idx + ch.len_utf8();
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        let text = &self.input[start..end];
+        let kind = Self::keyword(text).unwrap_or(TokenType::IDENTIFIER);
+        Token {
+            kind,
+            span: (start, end),
+        }
+    }
+
+    /// Scans a full numeric literal: `0x`/`0o`/`0b`-prefixed integers (their
+    /// own digit set, no fraction or exponent), or a decimal literal with an
+    /// optional fractional part and `e`/`E` exponent. Digit-group
+    /// underscores are allowed throughout. Only scans the span here; actual
+    /// conversion (and overflow/malformed-input checking) happens in
+    /// `numeric::parse_number_literal` once the parser has the text.
+    fn lex_number(&mut self, start: usize) -> Token {
+        let mut end = start;
+
+        let mut is_radix = false;
+        if let Some(&(idx, '0')) = self.chars.peek() {
+            end = idx + 1;
+            self.chars.next();
+            if let Some(&(_, prefix)) = self.chars.peek() {
+                if let Some(digit_ok) = radix_digit_predicate(prefix) {
+                    let (prefix_idx, prefix_ch) = self.chars.next().expect("just peeked");
+                    end = prefix_idx + prefix_ch.len_utf8();
+                    is_radix = true;
+                    end = self.consume_digits(end, digit_ok);
+                }
+            }
+        }
+
+        if !is_radix {
+            end = self.consume_digits(end, |c| c.is_ascii_digit());
+            end = self.consume_fraction(end);
+            end = self.consume_exponent(end);
+        }
+
+        Token {
+            kind: TokenType::NUM,
+            span: (start, end),
+        }
+    }
+
+    /// Consumes a run of `digit_ok` characters and `_` separators, returning
+    /// the new end offset (or `end` unchanged if nothing matched).
+    fn consume_digits(&mut self, mut end: usize, digit_ok: impl Fn(char) -> bool) -> usize {
+        while let Some(&(idx, ch)) = self.chars.peek() {
+            if digit_ok(ch) || ch == '_' {
+                end = idx + ch.len_utf8();
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        end
+    }
+
+    /// Consumes `.` followed by digits, but only when a digit actually
+    /// follows: a bare trailing `.` is left alone so it tokenizes as its own
+    /// `DOT`, the same way `5.foo` wouldn't be mistaken for a fraction.
+    fn consume_fraction(&mut self, end: usize) -> usize {
+        let Some(&(dot_idx, '.')) = self.chars.peek() else {
+            return end;
+        };
+        let mut lookahead = self.chars.clone();
+        lookahead.next();
+        if !matches!(lookahead.peek(), Some(&(_, c)) if c.is_ascii_digit()) {
+            return end;
+        }
+        self.chars.next();
+        self.consume_digits(dot_idx + 1, |c| c.is_ascii_digit())
+    }
+
+    /// Consumes `e`/`E`, an optional sign, and one or more digits, but only
+    /// when at least one exponent digit actually follows.
+    fn consume_exponent(&mut self, end: usize) -> usize {
+        let Some(&(e_idx, e_ch)) = self.chars.peek() else {
+            return end;
+        };
+        if e_ch != 'e' && e_ch != 'E' {
+            return end;
+        }
+        let mut lookahead = self.chars.clone();
+        lookahead.next();
+        let sign_len = match lookahead.peek() {
+            Some(&(_, '+')) | Some(&(_, '-')) => {
+                lookahead.next();
+                1
+            }
+            _ => 0,
+        };
+        if !matches!(lookahead.peek(), Some(&(_, c)) if c.is_ascii_digit()) {
+            return end;
+        }
+        self.chars.next(); // 'e'/'E'
+        let mut end = e_idx + e_ch.len_utf8();
+        if sign_len > 0 {
+            let (sign_idx, sign_ch) = self.chars.next().expect("lookahead just confirmed a sign");
+            end = sign_idx + sign_ch.len_utf8();
+        }
+        self.consume_digits(end, |c| c.is_ascii_digit())
+    }
+
+    fn lex_line_comment(&mut self, start: usize) -> Token {
+        let mut end = self.len;
+        while let Some(&(idx, ch)) = self.chars.peek() {
+            if ch == '\n' {
+                end = idx;
+                break;
+            }
+            self.chars.next();
+        }
+        Token {
+            kind: TokenType::LINE_COMMENT,
+            span: (start, end),
+        }
+    }
+
+    /// `/* ... */`, tracking depth so `/* /* */ */` closes on the outer `*/`.
+    fn lex_block_comment(&mut self, start: usize) -> Token {
+        let mut depth = 1usize;
+        while let Some(&(idx, ch)) = self.chars.peek() {
+            self.chars.next();
+            match ch {
+                '/' if self.chars.peek().map(|&(_, c)| c) == Some('*') => {
+                    self.chars.next();
+                    depth += 1;
+                }
+                '*' if self.chars.peek().map(|&(_, c)| c) == Some('/') => {
+                    self.chars.next();
+                    depth -= 1;
+                    if depth == 0 {
+                        return Token {
+                            kind: TokenType::BLOCK_COMMENT,
+                            span: (start, idx + 2),
+                        };
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.diagnostics.push(Diagnostic {
+            message: "unterminated block comment".to_string(),
+            span: (start, self.len),
+        });
+        Token {
+            kind: TokenType::ERROR,
+            span: (start, self.len),
+        }
+    }
+
+    fn lex_string(&mut self, start: usize) -> Token {
+        self.chars.next();
+        let mut end = self.len;
+        while let Some(&(idx, ch)) = self.chars.peek() {
+            self.chars.next();
+            if ch == '"' {
+                end = idx + 1;
+                break;
+            }
+        }
+        Token {
+            kind: TokenType::STR,
+            span: (start, end),
+        }
+    }
+
+    fn lex_symbol(&mut self, start: usize, ch: char) -> Token {
+        self.chars.next();
+        let two_char = |this: &mut Self, next: char, then: TokenType, otherwise: TokenType| {
+            if this.chars.peek().map(|&(_, c)| c) == Some(next) {
+                this.chars.next();
+                Token {
+                    kind: then,
+                    span: (start, start + 2),
+                }
+            } else {
+                Token {
+                    kind: otherwise,
+                    span: (start, start + 1),
+                }
+            }
+        };
+        match ch {
+            '+' => Token {
+                kind: TokenType::PLUS,
+                span: (start, start + 1),
+            },
+            '-' => Token {
+                kind: TokenType::MINUS,
+                span: (start, start + 1),
+            },
+            '*' => Token {
+                kind: TokenType::STAR,
+                span: (start, start + 1),
+            },
+            '%' => Token {
+                kind: TokenType::PERCENT,
+                span: (start, start + 1),
+            },
+            '=' => two_char(self, '=', TokenType::DOUBLE_EQUAL, TokenType::EQUAL),
+            '!' => two_char(self, '=', TokenType::BANG_EQUAL, TokenType::BANG),
+            '>' => two_char(self, '=', TokenType::GREATER_EQUAL, TokenType::GREATER),
+            '<' => two_char(self, '=', TokenType::LESSER_EQUAL, TokenType::LESSER),
+            '&' => Token {
+                kind: TokenType::AMPERSAND,
+                span: (start, start + 1),
+            },
+            '|' => Token {
+                kind: TokenType::PIPE,
+                span: (start, start + 1),
+            },
+            '(' => Token {
+                kind: TokenType::LPAREN,
+                span: (start, start + 1),
+            },
+            ')' => Token {
+                kind: TokenType::RPAREN,
+                span: (start, start + 1),
+            },
+            '{' => Token {
+                kind: TokenType::LBRACE,
+                span: (start, start + 1),
+            },
+            '}' => Token {
+                kind: TokenType::RBRACE,
+                span: (start, start + 1),
+            },
+            '[' => Token {
+                kind: TokenType::LBRACKET,
+                span: (start, start + 1),
+            },
+            ']' => Token {
+                kind: TokenType::RBRACKET,
+                span: (start, start + 1),
+            },
+            '.' => Token {
+                kind: TokenType::DOT,
+                span: (start, start + 1),
+            },
+            ':' => Token {
+                kind: TokenType::COLON,
+                span: (start, start + 1),
+            },
+            ',' => Token {
+                kind: TokenType::COMMA,
+                span: (start, start + 1),
+            },
+            ';' => Token {
+                kind: TokenType::EOL,
+                span: (start, start + 1),
+            },
+            unexpected => {
+                let len = unexpected.len_utf8();
+                self.diagnostics.push(Diagnostic {
+                    message: format!("unexpected character {unexpected:?}"),
+                    span: (start, start + len),
+                });
+                Token {
+                    kind: TokenType::ERROR,
+                    span: (start, start + len),
+                }
+            }
+        }
+    }
+}
+
+impl<'input> Iterator for Lexer<'input> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        loop {
+            let &(start, ch) = self.chars.peek()?;
+            match ch {
+                ' ' | '\t' | '\r' => {
+                    self.chars.next();
+                    continue;
+                }
+                '\n' => {
+                    self.chars.next();
+                    return Some(Token {
+                        kind: TokenType::EOL,
+                        span: (start, start + 1),
+                    });
+                }
+                '/' => {
+                    self.chars.next();
+                    match self.chars.peek().map(|&(_, c)| c) {
+                        Some('/') => {
+                            self.chars.next();
+                            return Some(self.lex_line_comment(start));
+                        }
+                        Some('*') => {
+                            self.chars.next();
+                            return Some(self.lex_block_comment(start));
+                        }
+                        _ => {
+                            return Some(Token {
+                                kind: TokenType::SLASH,
+                                span: (start, start + 1),
+                            })
+                        }
+                    }
+                }
+                '"' => return Some(self.lex_string(start)),
+                '0'..='9' => return Some(self.lex_number(start)),
+                c if c.is_alphabetic() || c == '_' => return Some(self.lex_identifier(start)),
+                c => return Some(self.lex_symbol(start, c)),
+            }
+        }
+    }
+}
@@ -1,12 +1,8 @@
-use core::panic;
-use std::iter::Peekable;
-
 use crate::ast_nodes::*;
-use crate::lang_errors::LangError;
-use crate::spans::*;
+use crate::lang_errors::{Diagnostic, Handler, LangError};
+use crate::spans::Span;
 use crate::token_lexer::Lexer;
 use crate::tokens::*;
-use colored::*;
 #[derive(Clone)]
 pub struct TokenIter<'input> {
     lexer: Lexer<'input>,
@@ -18,13 +14,21 @@ impl<'input> TokenIter<'input> {
             lexer: Lexer::new(input),
         }
     }
+
+    /// Diagnostics the lexer recorded while producing this stream
+    /// (unterminated block comments, unrecognized characters). Surfaced
+    /// here rather than printed directly so `Parser::batch_parse` can fold
+    /// them into the same `Handler` as every parse error.
+    pub fn lexer_diagnostics(&self) -> &[Diagnostic] {
+        self.lexer.diagnostics()
+    }
 }
 
 impl<'input> Iterator for TokenIter<'input> {
     type Item = Token;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.lexer.next()
+        self.lexer.by_ref().find(token_is_not_comment)
     }
 }
 #[derive(Clone)]
@@ -33,49 +37,120 @@ where
     I: Iterator<Item = Token>,
 {
     input: &'input str,
-    tokens: Peekable<I>,
-    err_out: LangError,
+    tokens: I,
+    peeked: Option<Token>,
+    handler: Handler,
+}
+
+/// Binding power a prefix (`not`/unary `-`) operator parses its operand at.
+/// Higher than every infix operator so `-a * b` groups as `(-a) * b`.
+const PREFIX_BP: u8 = 7;
+
+/// Binding power of the binary `op`, as `(left, right)`. The right power is
+/// one greater than the left for every operator here since they're all
+/// left-associative, so recursing on the right-hand side stops before a
+/// second operator of the same precedence level; a right-associative
+/// operator would use the same value for both.
+fn infix_binding_power(op: BinaryOp) -> (u8, u8) {
+    match op {
+        BinaryOp::OR => (1, 2),
+        BinaryOp::AND => (2, 3),
+        BinaryOp::GREATER
+        | BinaryOp::GREATER_EQUAL
+        | BinaryOp::LESSER
+        | BinaryOp::LESSER_EQUAL
+        | BinaryOp::ISEQUAL
+        | BinaryOp::ISDIFERENT => (3, 4),
+        BinaryOp::ADD | BinaryOp::SUBTRACT => (4, 5),
+        BinaryOp::MULTIPLY | BinaryOp::DIVIDE | BinaryOp::MODULO => (5, 6),
+    }
+}
+
+fn binop_for(kind: TokenType) -> Option<BinaryOp> {
+    Some(match kind {
+        TokenType::PLUS => BinaryOp::ADD,
+        TokenType::MINUS => BinaryOp::SUBTRACT,
+        TokenType::STAR => BinaryOp::MULTIPLY,
+        TokenType::SLASH => BinaryOp::DIVIDE,
+        TokenType::PERCENT => BinaryOp::MODULO,
+        TokenType::GREATER_EQUAL => BinaryOp::GREATER_EQUAL,
+        TokenType::GREATER => BinaryOp::GREATER,
+        TokenType::LESSER_EQUAL => BinaryOp::LESSER_EQUAL,
+        TokenType::LESSER => BinaryOp::LESSER,
+        TokenType::DOUBLE_EQUAL => BinaryOp::ISEQUAL,
+        TokenType::BANG_EQUAL => BinaryOp::ISDIFERENT,
+        TokenType::AND | TokenType::AMPERSAND => BinaryOp::AND,
+        TokenType::OR | TokenType::PIPE => BinaryOp::OR,
+        _ => return None,
+    })
 }
 
 impl<'input> Parser<'input, TokenIter<'input>> {
     pub fn new(input: &'input str) -> Parser<'input, TokenIter<'input>> {
         Parser {
             input,
-            tokens: TokenIter::new(input).peekable(),
-            err_out: LangError {
-                input: input.to_string(),
-            },
+            tokens: TokenIter::new(input),
+            peeked: None,
+            handler: Handler::new(),
         }
     }
     pub fn text(&mut self, token: &Token) -> String {
         return self.input[token.span.0..token.span.1].to_string();
     }
+    /// The span of a single point just past the end of the source, used for
+    /// diagnostics about tokens that were expected but never arrived.
+    fn eof_span(&self) -> Span {
+        (self.input.len(), self.input.len())
+    }
     fn peek(&mut self) -> Option<Token> {
-        self.tokens.peek().cloned()
+        if self.peeked.is_none() {
+            self.peeked = self.tokens.next();
+        }
+        self.peeked.clone()
     }
     fn peek_some(&mut self) -> Result<Token, ()> {
-        let Some(peeked) = self.tokens.peek().cloned() else {
-            println!(
-                "{} Expected to find another token but none was found",
-                "ERROR!".red()
-            );
-            return Err(());
+        let Some(peeked) = self.peek() else {
+            let span = self.eof_span();
+            return self.report_and_sync("expected to find another token but none was found", span);
         };
         return Ok(peeked);
     }
     fn next(&mut self) -> Option<Token> {
-        self.tokens.next()
+        self.peeked.take().or_else(|| self.tokens.next())
+    }
+    /// Skips tokens after an error until the next likely statement
+    /// boundary (`EOL`, `RBRACE`, or end of input), so the caller can keep
+    /// parsing instead of cascading the same error forever. Consumes the
+    /// `EOL` it stops at (if any) but leaves `RBRACE`/EOF for the caller to
+    /// see, the same way `parse_block` expects to find its own terminator.
+    fn sync(&mut self) {
+        while let Some(token) = self.peek() {
+            match token.kind {
+                TokenType::EOL => {
+                    self.next();
+                    return;
+                }
+                TokenType::RBRACE => return,
+                _ => {
+                    self.next();
+                }
+            }
+        }
+    }
+    /// Records a diagnostic, synchronizes to the next statement boundary,
+    /// and returns `Err(())`. The single place every parse error funnels
+    /// through so recovery always happens at the point of failure.
+    fn report_and_sync<T>(&mut self, message: impl Into<String>, span: Span) -> Result<T, ()> {
+        self.handler.report(message, span);
+        self.sync();
+        Err(())
     }
     fn check_valid(&mut self, expected: TokenType, token: Token) -> Result<(), ()> {
         if token.kind != expected {
-            println!();
-            let err = self.err_out.build(
-                format!("expected token {expected:?} but got token {:?}", token.kind).as_str(),
+            return self.report_and_sync(
+                format!("expected token {expected:?} but got token {:?}", token.kind),
                 token.span,
             );
-            println!("{err}");
-            println!();
-            return Err(());
         }
         Ok(())
     }
@@ -86,51 +161,46 @@ impl<'input> Parser<'input, TokenIter<'input>> {
         return Ok(token);
     }
     fn parse_vardef(&mut self) -> Result<NodeSpan, ()> {
-        let first = dbg!(self.peek());
         let ident: Token = self.expect(TokenType::IDENTIFIER)?;
         let var_name = self.text(&ident);
         self.next();
-        match self.peek_some()?.kind {
+        let next_token = self.peek_some()?;
+        match next_token.kind {
             TokenType::EOL => {
-                let Some(last) = self.peek() else {todo!()};
                 return Ok(Declaration {
                     var_name: var_name,
                     value: Box::new(Value::Null.to_nodespan(ident.span)),
                 }
-                .to_nodespan((first.expect("idk").span.0, last.span.1)));
+                .to_nodespan((ident.span.0, next_token.span.1)));
             }
             TokenType::EQUAL => {
-                let last = self.next();
+                self.next();
                 let val = self.parse_expr()?;
+                let val_end = val.span.1;
                 return Ok(Declaration {
                     var_name: var_name,
                     value: Box::new(val),
                 }
-                .to_nodespan((first.expect("idk").span.0, last.expect("idk").span.1)));
-            }
-            _ => {
-                let span = self.peek_some()?.span;
-                self.err_out.emit("Invalid variable declaration", span);
-                return Err(());
+                .to_nodespan((ident.span.0, val_end)));
             }
+            _ => self.report_and_sync("invalid variable declaration", next_token.span),
         }
     }
 
     fn parse_paren(&mut self, paren: Token) -> Result<NodeSpan, ()> {
         let expr = self.parse_expr()?;
-        let err = self.err_out.build("Unterminated parentheses", paren.span);
         let Some(end) = self.peek() else {
-            println!();
-            println!("{err}");
-            println!();
-            return Err(());
+            return self.report_and_sync("unterminated parentheses", paren.span);
         };
         self.check_valid(TokenType::RPAREN, end)?;
         self.next();
         return Ok(expr);
     }
     fn simple_parse(&mut self, peeked: &Option<Token>) -> Result<NodeSpan, ()> {
-        let Some(value) = peeked.clone() else {todo!()};
+        let Some(value) = peeked.clone() else {
+            let span = self.eof_span();
+            return self.report_and_sync("expected an expression but found end of input", span);
+        };
 
         match value.kind {
             TokenType::STR => {
@@ -140,7 +210,11 @@ impl<'input> Parser<'input, TokenIter<'input>> {
                 return self.parse_vardef();
             }
             TokenType::NUM => {
-                return Ok(Value::Num(self.text(&value).parse().unwrap()).to_nodespan(value.span));
+                let text = self.text(&value);
+                return match crate::numeric::parse_number_literal(&text) {
+                    Ok(n) => Ok(Value::Num(n).to_nodespan(value.span)),
+                    Err(message) => self.report_and_sync(message, value.span),
+                };
             }
             TokenType::FALSE => {
                 return Ok(Value::Bool(false).to_nodespan(value.span));
@@ -172,67 +246,232 @@ impl<'input> Parser<'input, TokenIter<'input>> {
                 }
                 .to_nodespan((first.span.0, span.1)));
             }
-            TokenType::NOT | TokenType::BANG => return self.unary_operator(UnaryOp::NOT),
-            TokenType::MINUS => return self.unary_operator(UnaryOp::NEGATIVE),
+            TokenType::IF => return self.parse_branch(value),
+            TokenType::FUNC => return self.parse_function(value),
+            TokenType::NOT | TokenType::BANG => return self.unary_operator(UnaryOp::NOT, value),
+            TokenType::MINUS => return self.unary_operator(UnaryOp::NEGATIVE, value),
             TokenType::LPAREN => return self.parse_paren(value),
+            TokenType::LBRACE => return self.parse_record(value),
             unexpected => {
-                panic!("{unexpected:?}");
+                return self.report_and_sync(
+                    format!("unexpected token {unexpected:?} in expression position"),
+                    value.span,
+                );
             }
         };
     }
-    fn parse_call(&mut self, callee: NodeSpan) -> Result<NodeSpan, ()> {
-        let mut params: NodeStream = vec![];
-        let first = self.next().expect("");
-        let mut token = dbg!(self.peek_some()?);
-        if token.kind == TokenType::RPAREN {
-            let last = self.next().expect("");
-            return Ok(Call {
-                args: Box::new(params),
-                callee: Box::new(callee),
+    /// `if <expr> { ... } [else { ... } | else if ...]`. `if_token` is the
+    /// already-consumed `if` keyword, used only for the resulting span.
+    fn parse_branch(&mut self, if_token: Token) -> Result<NodeSpan, ()> {
+        let condition = self.parse_expr()?;
+        let then_block = self.parse_block()?;
+        self.next(); // consume the then-block's closing brace
+        let mut span_end = then_block.span.1;
+
+        let else_block = if self.peek().map(|t| t.kind) == Some(TokenType::ELSE) {
+            self.next();
+            let else_branch = match self.peek_some()?.kind {
+                TokenType::IF => {
+                    let else_if = self.next().expect("peeked");
+                    self.parse_branch(else_if)?
+                }
+                _ => {
+                    let block = self.parse_block()?;
+                    self.next(); // consume the else-block's closing brace
+                    block
+                }
+            };
+            span_end = else_branch.span.1;
+            Some(Box::new(else_branch))
+        } else {
+            None
+        };
+
+        Ok(Branch {
+            condition: Box::new(condition),
+            then_block: Box::new(then_block),
+            else_block,
+        }
+        .to_nodespan((if_token.span.0, span_end)))
+    }
+
+    /// `func name(a, b, c) { ... }`. `func_token` is the already-consumed
+    /// `func` keyword, used only for the resulting span.
+    fn parse_function(&mut self, func_token: Token) -> Result<NodeSpan, ()> {
+        let ident = self.expect(TokenType::IDENTIFIER)?;
+        let name = self.text(&ident);
+        self.next();
+        self.expect(TokenType::LPAREN)?;
+        self.next();
+
+        let (params, _) = self.parse_paren_list(
+            "invalid token in function parameters",
+            |parser| {
+                let param = parser.expect(TokenType::IDENTIFIER)?;
+                let param_name = parser.text(&param);
+                parser.next();
+                Ok(param_name)
+            },
+        )?;
+
+        let body = self.parse_block()?;
+        self.next(); // consume the body's closing brace
+        let span = (func_token.span.0, body.span.1);
+        let Node::Block(block) = body.node else {
+            unreachable!("parse_block always produces a Block node")
+        };
+
+        Ok(Function {
+            name,
+            params,
+            body: Box::new(block),
+            captured: Vec::new(),
+        }
+        .to_nodespan(span))
+    }
+
+    /// `{ name: expr, name: expr }`, a record/map literal keyed by string.
+    /// `lbrace` is the already-consumed `{` token (mirrors `parse_paren`,
+    /// whose caller also advances past the dispatch token before calling in).
+    fn parse_record(&mut self, lbrace: Token) -> Result<NodeSpan, ()> {
+        let mut fields: Vec<(String, NodeSpan)> = vec![];
+        let mut token = self.peek_some()?;
+        if token.kind == TokenType::RBRACE {
+            let last = self
+                .next()
+                .expect("peek_some just confirmed a token is present");
+            return Ok(RecordLiteral { fields }.to_nodespan((lbrace.span.0, last.span.1)));
+        }
+        loop {
+            let key_token = self.expect(TokenType::IDENTIFIER)?;
+            let key = self.text(&key_token);
+            self.next();
+            self.expect(TokenType::COLON)?;
+            self.next();
+            let value = self.parse_expr()?;
+            fields.push((key, value));
+            token = self.peek_some()?;
+            match token.kind {
+                TokenType::RBRACE => break,
+                TokenType::COMMA => {
+                    self.next();
+                    token = self.peek_some()?;
+                    if token.kind == TokenType::RBRACE {
+                        break;
+                    }
+                }
+                _ => return self.report_and_sync("invalid token in record literal", token.span),
             }
-            .to_nodespan((first.span.0, last.span.1)));
         }
+        let last = self.peek_some()?;
+        self.next();
+        Ok(RecordLiteral { fields }.to_nodespan((lbrace.span.0, last.span.1)))
+    }
+
+    /// Resolves the chain of high-precedence postfix operators directly
+    /// after an atom: calls `(...)`, field access `.name`, and indexing
+    /// `[expr]`. Folded into one loop so `obj.a.b[c]()` chains naturally,
+    /// tighter than any binary operator.
+    fn parse_postfix(&mut self, mut left: NodeSpan) -> Result<NodeSpan, ()> {
+        loop {
+            let Some(token) = self.peek() else { break };
+            left = match token.kind {
+                TokenType::LPAREN => self.parse_call(left)?,
+                TokenType::DOT => self.parse_field_access(left)?,
+                TokenType::LBRACKET => self.parse_index(left)?,
+                _ => break,
+            };
+        }
+        Ok(left)
+    }
+
+    fn parse_field_access(&mut self, base: NodeSpan) -> Result<NodeSpan, ()> {
+        self.next()
+            .expect("caller just peeked a DOT token to get here");
+        let ident = self.expect(TokenType::IDENTIFIER)?;
+        let field = self.text(&ident);
+        self.next();
+        let span = (base.span.0, ident.span.1);
+        Ok(FieldAccess {
+            base: Box::new(base),
+            field,
+        }
+        .to_nodespan(span))
+    }
+
+    fn parse_index(&mut self, base: NodeSpan) -> Result<NodeSpan, ()> {
+        self.next()
+            .expect("caller just peeked an LBRACKET token to get here");
+        let index = self.parse_expr()?;
+        let end = self.expect(TokenType::RBRACKET)?;
+        self.next();
+        let span = (base.span.0, end.span.1);
+        Ok(Index {
+            base: Box::new(base),
+            index: Box::new(index),
+        }
+        .to_nodespan(span))
+    }
+
+    /// Parses a comma-separated `(item, item, ...)` list, where `element`
+    /// parses one item; the opening `(` must already be consumed. Returns
+    /// the items and the closing `RPAREN` token (for the caller's span).
+    /// Shared by `parse_call`'s arguments and `parse_function`'s
+    /// parameters, which differ only in what one element looks like.
+    fn parse_paren_list<T>(
+        &mut self,
+        error_message: &str,
+        mut element: impl FnMut(&mut Self) -> Result<T, ()>,
+    ) -> Result<(Vec<T>, Token), ()> {
+        let mut items: Vec<T> = vec![];
+        let mut token = self.peek_some()?;
         while token.kind != TokenType::RPAREN {
-            let expr = dbg!(self.parse_expr()?);
-            token = dbg!(self.peek_some()?);
+            let item = element(self)?;
+            token = self.peek_some()?;
             match token.kind {
                 TokenType::RPAREN => {
-                    params.push(expr);
+                    items.push(item);
                     break;
                 }
                 TokenType::COMMA => {
-                    params.push(expr);
+                    items.push(item);
                     self.next();
+                    token = self.peek_some()?;
                 }
-                _ => {
-                    self.err_out.emit("Invalid Token", token.span);
-                    return Err(());
-                }
+                _ => return self.report_and_sync(error_message.to_string(), token.span),
             }
         }
         let last = self.peek_some()?;
         self.next();
+        Ok((items, last))
+    }
+
+    fn parse_call(&mut self, callee: NodeSpan) -> Result<NodeSpan, ()> {
+        let first = self
+            .next()
+            .expect("caller just peeked an LPAREN to get here");
+        let (params, last) = self.parse_paren_list("invalid token in call arguments", Self::parse_expr)?;
 
-        let val = Call {
+        Ok(Call {
             args: Box::new(params),
             callee: Box::new(callee),
         }
-        .to_nodespan((first.span.0, last.span.1));
-        self.parse_operator(token, val)
+        .to_nodespan((first.span.0, last.span.1)))
     }
 
     pub fn parse_block(&mut self) -> Result<NodeSpan, ()> {
         let first = self.expect(TokenType::LBRACE)?;
         let mut body: NodeStream = vec![];
-        self.next().expect("");
-        let mut token = dbg!(self.peek_some()?);
+        self.next()
+            .expect("expect(LBRACE) just confirmed a token is present");
+        let mut token = self.peek_some()?;
 
         loop {
             if self.peek_some()?.kind == TokenType::RBRACE {
                 break;
             }
             let expr = self.parse_expr()?;
-            token = dbg!(self.peek_some()?);
+            token = self.peek_some()?;
 
             match token.kind {
                 TokenType::EOL => {
@@ -244,10 +483,9 @@ impl<'input> Parser<'input, TokenIter<'input>> {
                     body.push(expr.wrap_in_result());
                     break;
                 }
-                unexpected => self.err_out.emit(
-                    format!("Unexpected Token{unexpected:?}").as_str(),
-                    token.span,
-                ),
+                unexpected => {
+                    return self.report_and_sync(format!("unexpected token {unexpected:?} in block"), token.span)
+                }
             }
         }
         return Ok(Block {
@@ -255,71 +493,97 @@ impl<'input> Parser<'input, TokenIter<'input>> {
         }
         .to_nodespan((first.span.0, token.span.1)));
     }
-    fn unary_operator(&mut self, kind: UnaryOp) -> Result<NodeSpan, ()> {
-        let token = self.peek();
-        self.next();
-        let right = self.simple_parse(&token)?;
+    /// `not`/`!`/`-` prefixed unary expression. `token` is the
+    /// already-consumed NOT/BANG/MINUS token (mirrors `parse_paren`, whose
+    /// caller also advances past the dispatch token before calling in).
+    fn unary_operator(&mut self, kind: UnaryOp, token: Token) -> Result<NodeSpan, ()> {
+        let right = self.parse_expr_bp(PREFIX_BP)?;
+        let right_end = right.span.1;
         return Ok(UnaryNode {
             kind,
             object: Box::new(right),
         }
-        .to_nodespan((token.expect("").span)));
-    }
-    fn binary_operator(&mut self, left: NodeSpan, kind: BinaryOp) -> Result<NodeSpan, ()> {
-        let last = self.next();
-
-        return Ok(BinaryNode {
-            kind,
-            left: Box::new(left),
-            right: Box::new(self.parse_expr()?),
-        }
-        .to_nodespan(last.expect("fuck").span));
+        .to_nodespan((token.span.0, right_end)));
     }
-    fn parse_assignment(&mut self, previous: Token, token: Token) -> Result<NodeSpan, ()> {
-        self.check_valid(TokenType::IDENTIFIER, previous.clone())?;
-        let var_name = self.text(&previous);
-        let last = self.next();
+    /// `target = value`, where `target` is already the fully resolved
+    /// postfix chain on the left (bare variable or `.field`/`[index]`
+    /// chain); validity of the target is checked at eval time.
+    fn parse_assignment(&mut self, target: NodeSpan, token: Token) -> Result<NodeSpan, ()> {
+        let last = self
+            .next()
+            .expect("caller just peeked an EQUAL token to get here");
         return Ok(Assignment {
-            var_name,
+            target: Box::new(target),
             value: Box::new(self.parse_expr()?),
         }
-        .to_nodespan((token.span.0, last.expect("").span.1)));
-    }
-    fn parse_operator(&mut self, previous: Token, left: NodeSpan) -> Result<NodeSpan, ()> {
-        let Some(token) = self.peek() else {return Ok(left);};
-        match token.kind {
-            TokenType::EQUAL => return self.parse_assignment(previous, token),
-            TokenType::LPAREN => return self.parse_call(left),
-            TokenType::PLUS => return self.binary_operator(left, BinaryOp::ADD),
-            TokenType::MINUS => return self.binary_operator(left, BinaryOp::SUBTRACT),
-            TokenType::STAR => return self.binary_operator(left, BinaryOp::MULTIPLY),
-            TokenType::SLASH => return self.binary_operator(left, BinaryOp::DIVIDE),
-            TokenType::PERCENT => return self.binary_operator(left, BinaryOp::MODULO),
-            TokenType::GREATER_EQUAL => return self.binary_operator(left, BinaryOp::GREATER_EQUAL),
-            TokenType::GREATER => return self.binary_operator(left, BinaryOp::GREATER),
-            TokenType::LESSER_EQUAL => return self.binary_operator(left, BinaryOp::LESSER_EQUAL),
-            TokenType::LESSER => return self.binary_operator(left, BinaryOp::LESSER),
-            TokenType::DOUBLE_EQUAL => return self.binary_operator(left, BinaryOp::ISEQUAL),
-            TokenType::BANG_EQUAL => return self.binary_operator(left, BinaryOp::ISDIFERENT),
-
-            TokenType::AND | TokenType::AMPERSAND => {
-                return self.binary_operator(left, BinaryOp::AND)
+        .to_nodespan((token.span.0, last.span.1)));
+    }
+
+    /// Precedence-climbing expression parser: a prefix/atom on the left,
+    /// then `while` the next operator binds at least as tightly as
+    /// `min_bp`, fold it in and recurse on the right at its own binding
+    /// power. Calls, field access, and indexing are postfix operators
+    /// resolved immediately after the atom, always binding tighter than
+    /// any infix operator.
+    pub fn parse_expr(&mut self) -> Result<NodeSpan, ()> {
+        self.parse_expr_bp(0)
+    }
+
+    fn parse_expr_bp(&mut self, min_bp: u8) -> Result<NodeSpan, ()> {
+        let peeked = self.peek();
+        self.next();
+        let mut left = self.simple_parse(&peeked)?;
+        left = self.parse_postfix(left)?;
+
+        if let Some(token) = self.peek() {
+            if token.kind == TokenType::EQUAL {
+                return self.parse_assignment(left, token);
+            }
+        }
+
+        loop {
+            let Some(token) = self.peek() else { break };
+            let Some(op) = binop_for(token.kind) else { break };
+            let (left_bp, right_bp) = infix_binding_power(op);
+            if left_bp < min_bp {
+                break;
             }
-            TokenType::OR | TokenType::PIPE => return self.binary_operator(left, BinaryOp::OR),
-            _ => {
-                return Ok(left);
+            self.next();
+            let right = self.parse_expr_bp(right_bp)?;
+            let span = (left.span.0, right.span.1);
+            left = BinaryNode {
+                kind: op,
+                left: Box::new(left),
+                right: Box::new(right),
             }
+            .to_nodespan(span);
         }
+        Ok(left)
     }
 
-    pub fn parse_expr(&mut self) -> Result<NodeSpan, ()> {
-        let value = dbg!(self.peek());
-        self.next();
-        let left = self.simple_parse(&value)?;
-        let Some(peeked) = value else {todo!()};
-        self.parse_operator(peeked, left)
+    /// Parses a single expression, for contexts (the REPL) that don't need
+    /// a full top-level program.
+    pub fn batch_parse_expr(&mut self) -> Result<NodeSpan, LangError> {
+        let parsed = self.parse_expr();
+        for diagnostic in self.tokens.lexer_diagnostics() {
+            self.handler.report(diagnostic.message.clone(), diagnostic.span);
+        }
+        let first = self.handler.clone().into_diagnostics().into_iter().next();
+        match (parsed, first) {
+            (Ok(node), None) => Ok(node),
+            (_, Some(diagnostic)) => Err(LangError::new(diagnostic.message, diagnostic.span)),
+            (Err(()), None) => Err(LangError::new("failed to parse expression", (0, self.input.len()))),
+        }
     }
+
     pub fn parse_top(&mut self) -> Option<Result<NodeSpan, ()>> {
+        // A blank line between top-level statements is just a stray `EOL`
+        // left over after the previous statement consumed its own; skip
+        // any number of them instead of reporting each as a malformed
+        // statement.
+        while self.peek().map(|t| t.kind) == Some(TokenType::EOL) {
+            self.next();
+        }
         match self.peek()?.kind {
             TokenType::VAR => {
                 self.next();
@@ -327,19 +591,53 @@ impl<'input> Parser<'input, TokenIter<'input>> {
                 self.next();
                 var
             }
-            TokenType::FUNC => todo!(),
-            token => panic!("Invalid Token at toplevel: {token:?}"),
+            TokenType::FUNC => {
+                let func_token = self
+                    .next()
+                    .expect("peek() above just confirmed a token is present");
+                let func = self.parse_function(func_token);
+                // After a failed parse, `sync()` has already consumed the
+                // statement's `EOL`; only a `RBRACE` it stopped at is left.
+                if func.is_ok() || self.peek().map(|t| t.kind) == Some(TokenType::RBRACE) {
+                    self.next();
+                }
+                Some(func)
+            }
+            unexpected => {
+                let span = self
+                    .peek()
+                    .expect("peek() above just confirmed a token is present")
+                    .span;
+                let result = self.report_and_sync(format!("unexpected token {unexpected:?} at top level"), span);
+                // `sync()` leaves a `RBRACE` in place; at top level there is
+                // no block for it to close, so skip it here.
+                if self.peek().map(|t| t.kind) == Some(TokenType::RBRACE) {
+                    self.next();
+                }
+                Some(result)
+            }
         }
     }
-    pub fn batch_parse(&mut self) -> Block {
+
+    /// Parses every top-level statement, recovering from a malformed one
+    /// via `sync()` (triggered inside the failing parse routine) so a
+    /// single run reports every problem instead of stopping at the first.
+    pub fn batch_parse(&mut self) -> Result<Block, Vec<Diagnostic>> {
         let mut body: NodeStream = vec![];
-        loop {
-            let Some(parsed) = self.parse_top() else {break;};
-            let Ok(parsed_2) = parsed else {break;};
-            body.push(parsed_2);
+        while let Some(parsed) = self.parse_top() {
+            if let Ok(node) = parsed {
+                body.push(node);
+            }
+        }
+        for diagnostic in self.tokens.lexer_diagnostics() {
+            self.handler.report(diagnostic.message.clone(), diagnostic.span);
+        }
+        if self.handler.has_errors() {
+            Err(self.handler.clone().into_diagnostics())
+        } else {
+            Ok(Block {
+                body: Box::new(body),
+            })
         }
-        return Block {
-            body: Box::new(body),
-        };
     }
 }
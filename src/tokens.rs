@@ -0,0 +1,73 @@
+use crate::spans::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenType {
+    STR,
+    NUM,
+    IDENTIFIER,
+
+    TRUE,
+    FALSE,
+
+    VAR,
+    DO,
+    LOOP,
+    FUNC,
+    IF,
+    ELSE,
+
+    PLUS,
+    MINUS,
+    STAR,
+    SLASH,
+    PERCENT,
+
+    EQUAL,
+    DOUBLE_EQUAL,
+    BANG_EQUAL,
+    GREATER,
+    GREATER_EQUAL,
+    LESSER,
+    LESSER_EQUAL,
+
+    AND,
+    OR,
+    AMPERSAND,
+    PIPE,
+    NOT,
+    BANG,
+
+    LPAREN,
+    RPAREN,
+    LBRACE,
+    RBRACE,
+    LBRACKET,
+    RBRACKET,
+
+    DOT,
+    COLON,
+    COMMA,
+    EOL,
+
+    LINE_COMMENT,
+    BLOCK_COMMENT,
+
+    /// A lexical error (unterminated block comment, unrecognized
+    /// character, ...). Carries no payload; the message lives in the
+    /// `Diagnostic` the lexer records, which `TokenIter`/`Parser` surface
+    /// through the normal `Handler` machinery instead of panicking or
+    /// printing directly.
+    ERROR,
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub kind: TokenType,
+    pub span: Span,
+}
+
+/// Used to filter comment tokens out of the stream the parser sees, the
+/// same way whitespace never reaches it either.
+pub fn token_is_not_comment(token: &Token) -> bool {
+    !matches!(token.kind, TokenType::LINE_COMMENT | TokenType::BLOCK_COMMENT)
+}